@@ -1,53 +1,795 @@
+use std::collections::HashMap;
+use std::error::Error;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result;
-use std::num::ParseFloatError;
+use std::iter::Sum;
 use std::ops::AddAssign;
 use std::ops::MulAssign;
+use std::ops::Neg;
+use std::str::FromStr;
 
 // run unit tests with
 // cargo test -- amount
 
-#[derive(Eq, Hash, PartialEq)] // allows us to use Amount as a HashMap key
+/// The largest monetary value any amount is allowed to hold, in cents. Like
+/// the Bitcoin/Zcash amount modules we clamp to a sane supply so that checked
+/// arithmetic cannot wander off into meaningless territory.
+pub const MAX_MONEY: i64 = 21_000_000 * 100;
+
+/// The currency an [`Amount`] is denominated in. Amounts only combine with
+/// amounts of the same currency; a [`Bank`] is needed to cross between them.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Currency {
+    #[default]
+    USD,
+    EUR,
+    GBP,
+    CHF,
+    JPY,
+}
+
+impl Currency {
+    /// Recognise a trailing currency code, if any.
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "USD" => Some(Currency::USD),
+            "EUR" => Some(Currency::EUR),
+            "GBP" => Some(Currency::GBP),
+            "CHF" => Some(Currency::CHF),
+            "JPY" => Some(Currency::JPY),
+            _ => None,
+        }
+    }
+}
+
+/// The number of decimal places a plain cents-based amount carries.
+const CENT_SCALE: u32 = 2;
+
+/// 10^n as a wide integer, used to shift between decimal scales.
+fn pow10(n: u32) -> u128 {
+    return 10u128.pow(n);
+}
+
+/// A monetary value held as an exact integer `numerator` over `10^scale`, so
+/// the same type serves 2-decimal fiat and 8-decimal crypto assets without the
+/// sub-cent rounding a fixed `u32` of cents would force. `Eq`/`Hash` compare
+/// the reduced rational, so `1.50` and `1.5` are one key in a `HashMap`.
+// `Eq`/`Hash` are hand-implemented below (on the reduced rational) so Amount
+// can still serve as a HashMap key with value-based equality.
+#[derive(Clone, Copy, Debug)]
 pub struct Amount {
-    as_int: u32,
+    numerator: u64,
+    scale: u32,
+    currency: Currency,
 }
 
 impl Amount {
     fn new() -> Self {
-        return Amount { as_int: 0 };
+        return Amount {
+            numerator: 0,
+            scale: CENT_SCALE,
+            currency: Currency::default(),
+        };
+    }
+
+    /// Build an amount from a raw count of cents in the default currency.
+    pub fn from_cents(cents: u32) -> std::result::Result<Self, OutOfRangeError> {
+        return Amount::from_cents_in(cents, Currency::default());
+    }
+
+    /// Build an amount from cents tagged with `currency`, rejecting anything
+    /// above [`MAX_MONEY`].
+    pub fn from_cents_in(
+        cents: u32,
+        currency: Currency,
+    ) -> std::result::Result<Self, OutOfRangeError> {
+        return Amount::with_scale_in(cents as u64, CENT_SCALE, currency);
+    }
+
+    /// Build an amount from `numerator / 10^decimal_places` in the default
+    /// currency.
+    pub fn with_scale(
+        numerator: u64,
+        decimal_places: u32,
+    ) -> std::result::Result<Self, OutOfRangeError> {
+        return Amount::with_scale_in(numerator, decimal_places, Currency::default());
+    }
+
+    /// Build an amount from `numerator / 10^decimal_places` tagged with
+    /// `currency`, rejecting anything above [`MAX_MONEY`].
+    pub fn with_scale_in(
+        numerator: u64,
+        decimal_places: u32,
+        currency: Currency,
+    ) -> std::result::Result<Self, OutOfRangeError> {
+        check_bound(numerator, decimal_places)?;
+        return Ok(Amount {
+            numerator,
+            scale: decimal_places,
+            currency,
+        });
+    }
+
+    /// The currency this amount is denominated in.
+    pub fn currency(&self) -> Currency {
+        return self.currency;
+    }
+
+    /// The number of decimal places this amount is currently stored at.
+    pub fn decimal_places(&self) -> u32 {
+        return self.scale;
+    }
+
+    /// Re-express this amount at `decimal_places`, erroring if that would drop
+    /// non-zero digits (e.g. going from 8 to 2 places on a sub-cent value).
+    pub fn rescale(self, decimal_places: u32) -> std::result::Result<Self, RescaleError> {
+        let numerator = if decimal_places >= self.scale {
+            let factor = pow10(decimal_places - self.scale);
+            let widened = self.numerator as u128 * factor;
+            u64::try_from(widened).map_err(|_| RescaleError::Overflow)?
+        } else {
+            let factor = pow10(self.scale - decimal_places);
+            if !(self.numerator as u128).is_multiple_of(factor) {
+                return Err(RescaleError::PrecisionLoss);
+            }
+            (self.numerator as u128 / factor) as u64
+        };
+        return Amount::with_scale_in(numerator, decimal_places, self.currency)
+            .map_err(|_| RescaleError::Overflow);
+    }
+
+    /// The `(numerator, scale)` with trailing decimal zeros stripped, giving a
+    /// canonical form so equal values share one hash.
+    fn reduced(&self) -> (u64, u32) {
+        let mut numerator = self.numerator;
+        let mut scale = self.scale;
+        while scale > 0 && numerator.is_multiple_of(10) {
+            numerator /= 10;
+            scale -= 1;
+        }
+        return (numerator, scale);
+    }
+
+    /// Line two amounts up at a common scale, returning their aligned
+    /// numerators and that scale, or `None` on overflow.
+    fn align(self, rhs: Self) -> Option<(u128, u128, u32)> {
+        let scale = self.scale.max(rhs.scale);
+        let a = (self.numerator as u128).checked_mul(pow10(scale - self.scale))?;
+        let b = (rhs.numerator as u128).checked_mul(pow10(scale - rhs.scale))?;
+        return Some((a, b, scale));
+    }
+
+    /// Add two amounts, returning `None` on a currency mismatch or if the sum
+    /// leaves the valid range.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        if self.currency != rhs.currency {
+            return None;
+        }
+        let (a, b, scale) = self.align(rhs)?;
+        let numerator = u64::try_from(a + b).ok()?;
+        return Amount::with_scale_in(numerator, scale, self.currency).ok();
+    }
+
+    /// Subtract `rhs`, returning `None` on a currency mismatch or a negative
+    /// (unrepresentable) result.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        if self.currency != rhs.currency {
+            return None;
+        }
+        let (a, b, scale) = self.align(rhs)?;
+        let numerator = u64::try_from(a.checked_sub(b)?).ok()?;
+        return Amount::with_scale_in(numerator, scale, self.currency).ok();
+    }
+
+    /// Multiply by a scalar, returning `None` if the product leaves the range.
+    pub fn checked_mul(self, rhs: u32) -> Option<Self> {
+        let numerator = self.numerator.checked_mul(rhs as u64)?;
+        return Amount::with_scale_in(numerator, self.scale, self.currency).ok();
+    }
+
+    /// Reinterpret this amount as a debit/credit, failing out of range or when
+    /// the value carries sub-cent precision.
+    pub fn to_signed(self) -> std::result::Result<SignedAmount, OutOfRangeError> {
+        let cents = self.rescale(CENT_SCALE).map_err(|_| OutOfRangeError)?;
+        return SignedAmount::from_cents(cents.numerator as i64);
+    }
+}
+
+/// Check that `numerator / 10^scale` stays within [`MAX_MONEY`] (expressed in
+/// cents), comparing by cross-multiplication to stay on integer math.
+fn check_bound(numerator: u64, scale: u32) -> std::result::Result<(), OutOfRangeError> {
+    if numerator as u128 * 100 > MAX_MONEY as u128 * pow10(scale) {
+        return Err(OutOfRangeError);
+    }
+    return Ok(());
+}
+
+impl PartialEq for Amount {
+    fn eq(&self, other: &Self) -> bool {
+        return self.currency == other.currency && self.reduced() == other.reduced();
+    }
+}
+
+impl Eq for Amount {}
+
+impl std::hash::Hash for Amount {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // hash the reduced rational so the impl agrees with `PartialEq`.
+        self.currency.hash(state);
+        self.reduced().hash(state);
+    }
+}
+
+/// An exact rate as a numerator over a denominator, so conversions are
+/// reproducible rather than drifting the way repeated float scaling would.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Ratio {
+    numer: u64,
+    denom: u64,
+}
+
+impl Ratio {
+    /// Construct a rate `numer / denom`.
+    pub fn new(numer: u64, denom: u64) -> Self {
+        return Ratio { numer, denom };
+    }
+}
+
+/// Raised by [`Bank::reduce`] when no rate connects the two currencies, and
+/// also when the converted value falls outside the valid range.
+#[derive(Debug, Eq, PartialEq)]
+pub struct NoRateError {
+    from: Currency,
+    to: Currency,
+}
+
+impl Display for NoRateError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "no rate from {:?} to {:?}", self.from, self.to)
+    }
+}
+
+impl Error for NoRateError {}
+
+/// Holds directional exchange rates and reduces amounts into a target
+/// currency, following the classic "Money + Bank.reduce" design.
+#[derive(Default)]
+pub struct Bank {
+    rates: HashMap<(Currency, Currency), Ratio>,
+}
+
+impl Bank {
+    /// An empty bank that only knows the implicit identity rate.
+    pub fn new() -> Self {
+        return Bank { rates: HashMap::new() };
+    }
+
+    /// Record that one unit of `from` is worth `rate` units of `to`.
+    pub fn add_rate(&mut self, from: Currency, to: Currency, rate: Ratio) {
+        self.rates.insert((from, to), rate);
+    }
+
+    /// Convert `amount` into `to`. Same-currency reductions are the identity;
+    /// otherwise a rate must have been registered.
+    pub fn reduce(
+        &self,
+        amount: Amount,
+        to: Currency,
+    ) -> std::result::Result<Amount, NoRateError> {
+        if amount.currency == to {
+            return Ok(amount);
+        }
+        let rate = self.rates.get(&(amount.currency, to)).ok_or(NoRateError {
+            from: amount.currency,
+            to,
+        })?;
+        // round to the nearest unit at the amount's own scale using exact
+        // integer math, so a conversion is always reproducible.
+        let scaled = amount.numerator as u128 * rate.numer as u128;
+        let numerator = (scaled + rate.denom as u128 / 2) / rate.denom as u128;
+        let err = || NoRateError {
+            from: amount.currency,
+            to,
+        };
+        let numerator = u64::try_from(numerator).map_err(|_| err())?;
+        return Amount::with_scale_in(numerator, amount.scale, to).map_err(|_| err());
+    }
+}
+
+/// A monetary value that can be negative, for debits, credits and fee deltas.
+/// Construction and arithmetic keep the magnitude within [`MAX_MONEY`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SignedAmount {
+    as_int: i64,
+}
+
+impl SignedAmount {
+    /// Build a signed amount from cents, rejecting a magnitude above
+    /// [`MAX_MONEY`].
+    pub fn from_cents(cents: i64) -> std::result::Result<Self, OutOfRangeError> {
+        if !(-MAX_MONEY..=MAX_MONEY).contains(&cents) {
+            return Err(OutOfRangeError);
+        }
+        return Ok(SignedAmount { as_int: cents });
+    }
+
+    /// Add two signed amounts, returning `None` if the sum leaves the range.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let cents = self.as_int.checked_add(rhs.as_int)?;
+        return SignedAmount::from_cents(cents).ok();
+    }
+
+    /// Subtract `rhs`, returning `None` if the difference leaves the range.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let cents = self.as_int.checked_sub(rhs.as_int)?;
+        return SignedAmount::from_cents(cents).ok();
+    }
+
+    /// Multiply by a scalar, returning `None` if the product leaves the range.
+    pub fn checked_mul(self, rhs: i64) -> Option<Self> {
+        let cents = self.as_int.checked_mul(rhs)?;
+        return SignedAmount::from_cents(cents).ok();
+    }
+
+    /// Reinterpret this amount as unsigned, failing on a negative value.
+    pub fn to_unsigned(self) -> std::result::Result<Amount, OutOfRangeError> {
+        let cents = u32::try_from(self.as_int).map_err(|_| OutOfRangeError)?;
+        return Amount::from_cents(cents);
+    }
+}
+
+impl Neg for SignedAmount {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        // negation stays in range because the bound is symmetric.
+        return SignedAmount { as_int: -self.as_int };
+    }
+}
+
+// Summing yields an `Option` so a run past the monetary bound surfaces as
+// `None` rather than a silent wrap, mirroring the checked operators above.
+impl Sum<Amount> for Option<Amount> {
+    fn sum<I: Iterator<Item = Amount>>(iter: I) -> Self {
+        // seed from the first element so the currency is taken from the data,
+        // not the default; an empty iterator is the zero identity.
+        let mut iter = iter;
+        let mut total = match iter.next() {
+            Some(first) => first,
+            None => return Some(Amount::new()),
+        };
+        for a in iter {
+            total = total.checked_add(a)?;
+        }
+        return Some(total);
+    }
+}
+
+impl Sum<SignedAmount> for Option<SignedAmount> {
+    fn sum<I: Iterator<Item = SignedAmount>>(iter: I) -> Self {
+        let mut total = SignedAmount { as_int: 0 };
+        for a in iter {
+            total = total.checked_add(a)?;
+        }
+        return Some(total);
+    }
+}
+
+/// Raised when a value would fall outside the `-MAX_MONEY..=MAX_MONEY` range.
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutOfRangeError;
+
+impl Display for OutOfRangeError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "amount out of range")
+    }
+}
+
+impl Error for OutOfRangeError {}
+
+/// Reasons a [`Amount::rescale`] cannot be performed losslessly.
+#[derive(Debug, Eq, PartialEq)]
+pub enum RescaleError {
+    /// Shifting to fewer decimal places would discard non-zero digits.
+    PrecisionLoss,
+    /// The rescaled value no longer fits the underlying integer or range.
+    Overflow,
+}
+
+impl Display for RescaleError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            RescaleError::PrecisionLoss => write!(f, "rescale would lose precision"),
+            RescaleError::Overflow => write!(f, "rescaled value out of range"),
+        }
+    }
+}
+
+impl Error for RescaleError {}
+
+/// Reasons a string cannot be turned into an [`Amount`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum ParseAmountError {
+    /// A character that is not allowed where it appears: anything other than
+    /// a digit, a leading `+` or a single decimal point (a `-` included).
+    InvalidCharacter(char),
+    /// More fractional digits were supplied than the denomination allows.
+    TooPrecise,
+    /// The input was the empty string.
+    Empty,
+    /// The input carried a sign and/or a decimal point but no digits.
+    MissingDigits,
+    /// The value does not fit in the underlying integer or exceeds
+    /// [`MAX_MONEY`].
+    Overflow,
+}
+
+impl Display for ParseAmountError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            ParseAmountError::InvalidCharacter(c) => write!(f, "invalid character: {}", c),
+            ParseAmountError::TooPrecise => write!(f, "too many fractional digits"),
+            ParseAmountError::Empty => write!(f, "empty input string"),
+            ParseAmountError::MissingDigits => write!(f, "no digits found"),
+            ParseAmountError::Overflow => write!(f, "value out of range"),
+        }
+    }
+}
+
+impl Error for ParseAmountError {}
+
+/// A unit an [`Amount`] can be parsed from or displayed in: major units
+/// (e.g. `"44.12 USD"`) or minor units (e.g. `"4412c"`). A denomination's
+/// `precision` is the number of decimal places it sits above the minor unit
+/// (2 for major, 0 for minor), and is the offset the decimal point is shifted
+/// by when converting.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Denomination {
+    /// Whole currency units, two decimal places (dollars, euros, ...).
+    Major,
+    /// The smallest unit, no decimal places (cents).
+    Minor,
+}
+
+impl Denomination {
+    /// How far the decimal point shifts between this unit and the minor unit.
+    pub fn precision(&self) -> u32 {
+        match self {
+            Denomination::Major => CENT_SCALE,
+            Denomination::Minor => 0,
+        }
+    }
+
+    /// Recognise a trailing minor-unit token, if any.
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "c" | "cents" => Some(Denomination::Minor),
+            _ => None,
+        }
+    }
+}
+
+// Scan a decimal string into minor units (cents) without touching a float, so
+// values like `0.005` can no longer be silently rounded. We read the string
+// once, accumulating the integer part into a u64 and then at most `precision`
+// fractional digits once we have seen the `.`, then fold the integer part up
+// by `10^precision`.
+fn parse_minor_units(input_string: &str, precision: u32) -> std::result::Result<u64, ParseAmountError> {
+    if input_string.is_empty() {
+        return Err(ParseAmountError::Empty);
+    }
+
+    let mut chars = input_string.chars();
+    let mut next = chars.next();
+
+    // optionally consume a leading sign; a negative value cannot fit an
+    // unsigned Amount, so we only tolerate a redundant `+` here.
+    match next {
+        Some('+') => next = chars.next(),
+        // a negative sign is a value error on an unsigned parse, not an
+        // "doesn't fit the integer" overflow.
+        Some('-') => return Err(ParseAmountError::InvalidCharacter('-')),
+        _ => {}
+    }
+
+    let mut acc: u64 = 0;
+    let mut frac: u64 = 0;
+    let mut saw_digit = false;
+    let mut frac_digits = 0u32;
+    let mut seen_point = false;
+
+    while let Some(c) = next {
+        match c {
+            '0'..='9' => {
+                let digit = (c as u8 - b'0') as u64;
+                if seen_point {
+                    if frac_digits == precision {
+                        return Err(ParseAmountError::TooPrecise);
+                    }
+                    frac = frac * 10 + digit;
+                    frac_digits += 1;
+                } else {
+                    acc = acc
+                        .checked_mul(10)
+                        .and_then(|a| a.checked_add(digit))
+                        .ok_or(ParseAmountError::Overflow)?;
+                }
+                saw_digit = true;
+            }
+            '.' if !seen_point => seen_point = true,
+            _ => return Err(ParseAmountError::InvalidCharacter(c)),
+        }
+        next = chars.next();
+    }
+
+    if !saw_digit {
+        return Err(ParseAmountError::MissingDigits);
+    }
+
+    // left-align the fraction to `precision` places then fold the integer part
+    // up by the same power of ten and add it in.
+    for _ in frac_digits..precision {
+        frac *= 10;
+    }
+    let units = acc
+        .checked_mul(pow10(precision) as u64)
+        .and_then(|a| a.checked_add(frac))
+        .ok_or(ParseAmountError::Overflow)?;
+
+    return Ok(units);
+}
+
+impl Amount {
+    /// Parse a string in `denom`, honouring an optional trailing denomination
+    /// token (e.g. `"4412c"` or `"44.12 USD"`) which overrides `denom`.
+    pub fn from_str_in(s: &str, denom: Denomination) -> std::result::Result<Self, ParseAmountError> {
+        let (number, denom, currency) = split_denomination(s, denom);
+        let cents = parse_minor_units(number, denom.precision())?;
+        // route through the checked constructor so the parse path cannot mint
+        // an amount above MAX_MONEY.
+        return Amount::with_scale_in(cents, CENT_SCALE, currency)
+            .map_err(|_| ParseAmountError::Overflow);
+    }
+
+    /// Render this amount in `denom`, shifting the decimal point by the
+    /// denomination's precision.
+    pub fn to_string_in(&self, denom: Denomination) -> String {
+        return self.display_in(denom).to_string();
+    }
+
+    /// A `Display`-able view of this amount in `denom`.
+    pub fn display_in(&self, denom: Denomination) -> DisplayIn<'_> {
+        return DisplayIn { amount: self, denom };
+    }
+
+    /// The value as an integer count of minor units (cents), truncating any
+    /// sub-cent remainder the representation may carry.
+    fn to_minor_units(self) -> u64 {
+        if self.scale <= CENT_SCALE {
+            return self.numerator * pow10(CENT_SCALE - self.scale) as u64;
+        }
+        return self.numerator / pow10(self.scale - CENT_SCALE) as u64;
+    }
+}
+
+/// Strip a recognised trailing denomination token, returning the numeric part,
+/// the denomination to read it in (falling back to `default`) and the currency
+/// the token names (falling back to the default currency). A currency token
+/// such as `"EUR"` both selects major units and tags the amount, rather than
+/// being silently dropped.
+fn split_denomination(s: &str, default: Denomination) -> (&str, Denomination, Currency) {
+    let s = s.trim();
+    if let Some(number) = s.strip_suffix('c') {
+        return (number.trim_end(), Denomination::Minor, Currency::default());
+    }
+    if let Some(idx) = s.rfind(char::is_whitespace) {
+        let (number, token) = s.split_at(idx);
+        let token = token.trim();
+        if let Some(currency) = Currency::from_token(token) {
+            return (number.trim_end(), Denomination::Major, currency);
+        }
+        if let Some(denom) = Denomination::from_token(token) {
+            return (number.trim_end(), denom, Currency::default());
+        }
+    }
+    return (s, default, Currency::default());
+}
+
+/// A `Display` wrapper that prints an [`Amount`] in a chosen [`Denomination`].
+pub struct DisplayIn<'a> {
+    amount: &'a Amount,
+    denom: Denomination,
+}
+
+impl Display for DisplayIn<'_> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let cents = self.amount.to_minor_units();
+        let precision = self.denom.precision();
+        let divisor = pow10(precision) as u64;
+        let quot_x = cents / divisor;
+        let rem_x = cents % divisor;
+
+        if precision == 0 {
+            write!(f, "{}c", quot_x)
+        } else {
+            write!(f, "{}.{:0width$}", quot_x, rem_x, width = precision as usize)
+        }
     }
+}
+
+impl FromStr for Amount {
+    type Err = ParseAmountError;
+
+    fn from_str(input_string: &str) -> std::result::Result<Self, Self::Err> {
+        return Amount::from_str_in(input_string, Denomination::Major);
+    }
+}
+
+// serde support, behind the `serde` feature. Following the rust-bitcoin
+// convention we serialize a human-readable decimal string for formats like
+// JSON and a compact `(numerator, scale)` pair for binary formats, choosing
+// via `is_human_readable`. Both forms preserve the full precision chunk0-4
+// gave the type, so the round trip is lossless even above cent precision.
+// The currency tag is not serialized: a deserialized amount is always in the
+// default currency.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Amount {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            use serde::ser::SerializeTuple;
+            let mut tuple = serializer.serialize_tuple(2)?;
+            tuple.serialize_element(&self.numerator)?;
+            tuple.serialize_element(&self.scale)?;
+            tuple.end()
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Amount {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        use serde::de::Error;
+        if deserializer.is_human_readable() {
+            let text = String::deserialize(deserializer)?;
+            Amount::from_decimal_str(&text).map_err(D::Error::custom)
+        } else {
+            let (numerator, scale) = <(u64, u32)>::deserialize(deserializer)?;
+            Amount::with_scale(numerator, scale).map_err(D::Error::custom)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Amount {
+    /// Parse a decimal string at its full precision, taking the scale from the
+    /// number of fractional digits, so `"0.00000001"` round-trips losslessly.
+    fn from_decimal_str(text: &str) -> std::result::Result<Self, ParseAmountError> {
+        if text.is_empty() {
+            return Err(ParseAmountError::Empty);
+        }
+        let mut chars = text.chars();
+        let mut next = chars.next();
+        match next {
+            Some('+') => next = chars.next(),
+            Some('-') => return Err(ParseAmountError::InvalidCharacter('-')),
+            _ => {}
+        }
 
-    fn new_from_str(input_string: &str) -> Self {
-        let float_from_input = input_string.parse::<f32>();
-        let float_res = match float_from_input {
-            Ok(number_to_round) => number_to_round,
-            Err(ParseFloatError) => panic!("Input string {} doesn't parse as f32", input_string),
+        let mut numerator: u64 = 0;
+        let mut scale = 0u32;
+        let mut saw_digit = false;
+        let mut seen_point = false;
+
+        while let Some(c) = next {
+            match c {
+                '0'..='9' => {
+                    let digit = (c as u8 - b'0') as u64;
+                    numerator = numerator
+                        .checked_mul(10)
+                        .and_then(|a| a.checked_add(digit))
+                        .ok_or(ParseAmountError::Overflow)?;
+                    if seen_point {
+                        scale += 1;
+                    }
+                    saw_digit = true;
+                }
+                '.' if !seen_point => seen_point = true,
+                _ => return Err(ParseAmountError::InvalidCharacter(c)),
+            }
+            next = chars.next();
+        }
+
+        if !saw_digit {
+            return Err(ParseAmountError::MissingDigits);
+        }
+
+        return Amount::with_scale_in(numerator, scale, Currency::default())
+            .map_err(|_| ParseAmountError::Overflow);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SignedAmount {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_decimal_string())
+        } else {
+            serializer.serialize_i64(self.as_int)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SignedAmount {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        use serde::de::Error;
+        if deserializer.is_human_readable() {
+            let text = String::deserialize(deserializer)?;
+            SignedAmount::from_decimal_string(&text).map_err(D::Error::custom)
+        } else {
+            let cents = i64::deserialize(deserializer)?;
+            SignedAmount::from_cents(cents).map_err(D::Error::custom)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl SignedAmount {
+    /// The signed decimal representation, e.g. `"-44.12"`.
+    fn to_decimal_string(self) -> String {
+        let sign = if self.as_int < 0 { "-" } else { "" };
+        let magnitude = self.as_int.unsigned_abs();
+        return format!("{}{}.{:02}", sign, magnitude / 100, magnitude % 100);
+    }
+
+    /// Parse a signed decimal string through the unsigned [`Amount`] parser.
+    fn from_decimal_string(text: &str) -> std::result::Result<Self, ParseAmountError> {
+        let (negative, body) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
         };
-        let float_times_hundred = float_res * 100.0;
-        let int_res = float_times_hundred.round() as u32;
-        return Amount { as_int: int_res };
+        let magnitude = Amount::from_str(body)?.to_minor_units() as i64;
+        let cents = if negative { -magnitude } else { magnitude };
+        return SignedAmount::from_cents(cents).map_err(|_| ParseAmountError::Overflow);
     }
 }
 
 impl AddAssign for Amount {
     fn add_assign(&mut self, other_amount: Self) {
-        self.as_int += other_amount.as_int;
+        // the operator is unchecked on overflow like the original, but adding
+        // across currencies corrupts the value, so reject it hard; callers who
+        // want a fallible path use `checked_add`.
+        assert_eq!(
+            self.currency, other_amount.currency,
+            "cannot add amounts of different currencies"
+        );
+        // scale-aware so values at different precisions still line up.
+        let (a, b, scale) = self.align(other_amount).unwrap();
+        self.numerator = (a + b) as u64;
+        self.scale = scale;
     }
 }
 
 impl MulAssign<u32> for Amount {
     fn mul_assign(&mut self, multiplier: u32) {
-        self.as_int *= multiplier;
+        self.numerator *= multiplier as u64;
     }
 }
 
 impl Display for Amount {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        let quot_x = self.as_int.checked_div(100).unwrap();
-        let rem_x = self.as_int.checked_rem(100).unwrap();
+        let divisor = pow10(self.scale) as u64;
+        let quot_x = self.numerator / divisor;
+        let rem_x = self.numerator % divisor;
 
-        write!(f, "{}.{}", quot_x, rem_x)
+        if self.scale == 0 {
+            write!(f, "{}", quot_x)
+        } else {
+            write!(f, "{}.{:0width$}", quot_x, rem_x, width = self.scale as usize)
+        }
     }
 }
 
@@ -58,41 +800,278 @@ mod tests {
     use super::*;
     #[test]
     fn constructor_from_str_works() {
-        let am = Amount::new_from_str(&"44.12");
-        assert_eq!(am.as_int, 4412);
+        let am = Amount::from_str("44.12").unwrap();
+        assert_eq!(am.numerator, 4412);
     }
 
     #[test]
     fn constructor_default_works() {
         let am = Amount::new();
-        assert_eq!(am.as_int, 0);
+        assert_eq!(am.numerator, 0);
+    }
+
+    #[test]
+    fn parses_integer_without_point() {
+        let am = Amount::from_str("44").unwrap();
+        assert_eq!(am.numerator, 4400);
     }
 
     #[test]
-    #[should_panic]
-    fn bad_constructor_panics() {
-        Amount::new(&"asda");
+    fn parses_single_fractional_digit() {
+        let am = Amount::from_str("44.1").unwrap();
+        assert_eq!(am.numerator, 4410);
+    }
+
+    #[test]
+    fn does_not_round_through_float() {
+        // 0.005 used to round up to a cent once it went through f32.
+        assert_eq!(Amount::from_str("0.00"), Ok(Amount::from_cents(0).unwrap()));
+        assert_eq!(Amount::from_str("0.005"), Err(ParseAmountError::TooPrecise));
+    }
+
+    #[test]
+    fn bad_constructor_errors() {
+        assert_eq!(
+            Amount::from_str("asda"),
+            Err(ParseAmountError::InvalidCharacter('a'))
+        );
+    }
+
+    #[test]
+    fn empty_string_errors() {
+        assert_eq!(Amount::from_str(""), Err(ParseAmountError::Empty));
+    }
+
+    #[test]
+    fn sign_only_errors() {
+        assert_eq!(Amount::from_str("+"), Err(ParseAmountError::MissingDigits));
     }
 
     #[test]
     fn multiply_by_zero() {
-        let mut am = Amount::new(&"44.12");
+        let mut am = Amount::from_str("44.12").unwrap();
         am *= 0;
-        assert_eq!(am.as_int, 0);
+        assert_eq!(am.numerator, 0);
     }
 
     #[test]
     fn multiply_by_ten() {
-        let mut am = Amount::new(&"44.12");
+        let mut am = Amount::from_str("44.12").unwrap();
         am *= 10;
-        assert_eq!(am.as_int, 44120);
+        assert_eq!(am.numerator, 44120);
     }
 
     #[test]
     fn add_two_amounts() {
-        let mut am1 = Amount::new(&"44.12");
-        let am2 = Amount::new(&"45.80");
+        let mut am1 = Amount::from_str("44.12").unwrap();
+        let am2 = Amount::from_str("45.80").unwrap();
         am1 += am2;
-        assert_eq!(am1.as_int, 8992);
+        assert_eq!(am1.numerator, 8992);
+    }
+
+    #[test]
+    fn checked_sub_goes_negative() {
+        let small = Amount::from_cents(100).unwrap();
+        let big = Amount::from_cents(200).unwrap();
+        assert_eq!(small.checked_sub(big), None);
+        assert_eq!(big.checked_sub(small), Amount::from_cents(100).ok());
+    }
+
+    #[test]
+    fn checked_ops_respect_max_money() {
+        let near = Amount::from_cents(MAX_MONEY as u32).unwrap();
+        assert_eq!(near.checked_add(Amount::from_cents(1).unwrap()), None);
+        assert!(Amount::from_cents(MAX_MONEY as u32 + 1).is_err());
+    }
+
+    #[test]
+    fn signed_neg_and_roundtrip() {
+        let debit = -SignedAmount::from_cents(4412).unwrap();
+        assert_eq!(debit.as_int, -4412);
+        assert_eq!(debit.to_unsigned(), Err(OutOfRangeError));
+        assert_eq!((-debit).to_unsigned(), Amount::from_cents(4412));
+    }
+
+    #[test]
+    fn signed_sum_reports_overflow() {
+        let ok = vec![
+            SignedAmount::from_cents(100).unwrap(),
+            SignedAmount::from_cents(-40).unwrap(),
+        ];
+        assert_eq!(
+            ok.into_iter().sum::<Option<SignedAmount>>(),
+            SignedAmount::from_cents(60).ok()
+        );
+
+        let over = vec![
+            SignedAmount::from_cents(MAX_MONEY).unwrap(),
+            SignedAmount::from_cents(MAX_MONEY).unwrap(),
+        ];
+        assert_eq!(over.into_iter().sum::<Option<SignedAmount>>(), None);
+    }
+
+    #[test]
+    fn sum_works_for_non_default_currency() {
+        let eur = vec![
+            Amount::from_cents_in(100, Currency::EUR).unwrap(),
+            Amount::from_cents_in(200, Currency::EUR).unwrap(),
+        ];
+        assert_eq!(
+            eur.into_iter().sum::<Option<Amount>>(),
+            Amount::from_cents_in(300, Currency::EUR).ok()
+        );
+    }
+
+    #[test]
+    fn mismatched_currency_does_not_add() {
+        let usd = Amount::from_cents_in(100, Currency::USD).unwrap();
+        let eur = Amount::from_cents_in(100, Currency::EUR).unwrap();
+        assert_eq!(usd.checked_add(eur), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "different currencies")]
+    fn add_assign_across_currencies_panics() {
+        let mut usd = Amount::from_cents_in(100, Currency::USD).unwrap();
+        let eur = Amount::from_cents_in(100, Currency::EUR).unwrap();
+        usd += eur;
+    }
+
+    #[test]
+    fn bank_reduces_across_currencies() {
+        let mut bank = Bank::new();
+        // 1 USD buys 0.9 EUR.
+        bank.add_rate(Currency::USD, Currency::EUR, Ratio::new(9, 10));
+        let usd = Amount::from_cents_in(1000, Currency::USD).unwrap();
+        let eur = bank.reduce(usd, Currency::EUR).unwrap();
+        assert_eq!(eur, Amount::from_cents_in(900, Currency::EUR).unwrap());
+    }
+
+    #[test]
+    fn bank_identity_rate_is_implicit() {
+        let bank = Bank::new();
+        let usd = Amount::from_cents_in(1234, Currency::USD).unwrap();
+        assert_eq!(bank.reduce(usd, Currency::USD), Ok(usd));
+    }
+
+    #[test]
+    fn with_scale_supports_eight_decimals() {
+        // one satoshi of an 8-decimal asset.
+        let sat = Amount::with_scale(1, 8).unwrap();
+        assert_eq!(sat.decimal_places(), 8);
+        assert_eq!(format!("{}", sat), "0.00000001");
+    }
+
+    #[test]
+    fn rescale_errors_on_precision_loss() {
+        let sub_cent = Amount::with_scale(12345, 8).unwrap();
+        assert_eq!(sub_cent.rescale(2), Err(RescaleError::PrecisionLoss));
+
+        let exact = Amount::with_scale(4412_000000, 8).unwrap();
+        assert_eq!(exact.rescale(2).unwrap(), Amount::from_cents(4412).unwrap());
+    }
+
+    #[test]
+    fn equal_values_at_different_scales_share_a_hash_key() {
+        use std::collections::HashMap;
+        let coarse = Amount::with_scale(15, 1).unwrap(); // 1.5
+        let fine = Amount::with_scale(150, 2).unwrap(); // 1.50
+        assert_eq!(coarse, fine);
+
+        let mut map: HashMap<Amount, &str> = HashMap::new();
+        map.insert(coarse, "first");
+        map.insert(fine, "second");
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(&coarse), Some(&"second"));
+    }
+
+    #[test]
+    fn parses_and_formats_in_denominations() {
+        let from_major = Amount::from_str_in("44.12 USD", Denomination::Major).unwrap();
+        let from_minor = Amount::from_str_in("4412c", Denomination::Minor).unwrap();
+        assert_eq!(from_major, from_minor);
+        assert_eq!(from_major.to_string_in(Denomination::Major), "44.12");
+        assert_eq!(from_major.to_string_in(Denomination::Minor), "4412c");
+    }
+
+    #[test]
+    fn trailing_token_overrides_denomination() {
+        // the `c` token wins even though we asked for major units.
+        let am = Amount::from_str_in("4412c", Denomination::Major).unwrap();
+        assert_eq!(am, Amount::from_cents(4412).unwrap());
+    }
+
+    #[test]
+    fn minor_denomination_rejects_fraction() {
+        assert_eq!(
+            Amount::from_str_in("44.1", Denomination::Minor),
+            Err(ParseAmountError::TooPrecise)
+        );
+    }
+
+    #[test]
+    fn currency_token_tags_the_amount() {
+        let eur = Amount::from_str_in("44.12 EUR", Denomination::Major).unwrap();
+        assert_eq!(eur, Amount::from_cents_in(4412, Currency::EUR).unwrap());
+    }
+
+    #[test]
+    fn from_str_respects_max_money() {
+        // 30e9 cents is above MAX_MONEY and must not slip through the parser.
+        assert_eq!(Amount::from_str("300000000"), Err(ParseAmountError::Overflow));
+    }
+
+    #[test]
+    fn add_aligns_differing_scales() {
+        let mut coarse = Amount::with_scale(15, 1).unwrap(); // 1.5
+        let fine = Amount::with_scale(150, 2).unwrap(); // 1.50
+        coarse += fine;
+        assert_eq!(coarse, Amount::with_scale(300, 2).unwrap()); // 3.00
+    }
+
+    #[test]
+    fn bank_without_rate_errors() {
+        let bank = Bank::new();
+        let usd = Amount::from_cents_in(100, Currency::USD).unwrap();
+        assert_eq!(
+            bank.reduce(usd, Currency::GBP),
+            Err(NoRateError {
+                from: Currency::USD,
+                to: Currency::GBP,
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn amount_json_round_trip_is_human_readable() {
+        let am = Amount::from_cents(4412).unwrap();
+        let json = serde_json::to_string(&am).unwrap();
+        assert_eq!(json, "\"44.12\"");
+        assert_eq!(serde_json::from_str::<Amount>(&json).unwrap(), am);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn amount_round_trip_is_lossless_above_cents() {
+        // one satoshi of an 8-decimal asset must survive both encodings.
+        let sat = Amount::with_scale(1, 8).unwrap();
+
+        let json = serde_json::to_string(&sat).unwrap();
+        assert_eq!(json, "\"0.00000001\"");
+        assert_eq!(serde_json::from_str::<Amount>(&json).unwrap(), sat);
+
+        // a non-human-readable format keeps the (numerator, scale) pair.
+        let bytes = bincode::serialize(&sat).unwrap();
+        assert_eq!(bincode::deserialize::<Amount>(&bytes).unwrap(), sat);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn signed_amount_json_round_trip() {
+        let debit = -SignedAmount::from_cents(4412).unwrap();
+        let json = serde_json::to_string(&debit).unwrap();
+        assert_eq!(json, "\"-44.12\"");
+        assert_eq!(serde_json::from_str::<SignedAmount>(&json).unwrap(), debit);
     }
 }
@@ -0,0 +1,5 @@
+// the repo leans on explicit `return` statements throughout; keep clippy from
+// fighting that house style.
+#![allow(clippy::needless_return)]
+
+pub mod amount;